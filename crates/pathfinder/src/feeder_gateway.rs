@@ -0,0 +1,341 @@
+//! A minimal HTTP server exposing the classic feeder-gateway class-sync
+//! routes, so that other nodes and tooling speaking the gateway protocol can
+//! pull classes directly from this pathfinder node instead of the central
+//! sequencer.
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use pathfinder_common::{BlockId, ClassHash, ContractAddress};
+use warp::Filter;
+
+use crate::rpc::v02::RpcContext;
+
+/// Configuration for the feeder-gateway compatibility server. The server is
+/// only started when `enabled` is `true`, and then listens on `addr`.
+#[derive(Clone, Debug)]
+pub struct FeederGatewayConfig {
+    pub enabled: bool,
+    pub addr: SocketAddr,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FeederGatewayError {
+    #[error("Block not found")]
+    BlockNotFound,
+    #[error("Class hash not found")]
+    ClassHashNotFound,
+    #[error("Invalid block id")]
+    InvalidBlockId,
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl warp::reject::Reject for FeederGatewayError {}
+
+/// Starts the feeder-gateway compatibility server, if enabled. Returns
+/// immediately if `config.enabled` is `false`.
+pub async fn run(context: RpcContext, config: FeederGatewayConfig) -> anyhow::Result<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let routes = routes(context);
+    warp::serve(routes).run(config.addr).await;
+
+    Ok(())
+}
+
+fn routes(
+    context: RpcContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    let ctx = warp::any().map(move || context.clone());
+
+    let get_class_by_hash = warp::path!("feeder_gateway" / "get_class_by_hash")
+        .and(warp::get())
+        .and(warp::query::<ClassByHashQuery>())
+        .and(ctx.clone())
+        .and_then(|query: ClassByHashQuery, context: RpcContext| async move {
+            get_class_by_hash(context, query.class_hash)
+                .await
+                .map(class_definition_reply)
+                .map_err(warp::reject::custom)
+        });
+
+    let get_class_by_address = warp::path!("feeder_gateway" / "get_full_contract")
+        .and(warp::get())
+        .and(warp::query::<ClassAtAddressQuery>())
+        .and(ctx.clone())
+        .and_then(
+            |query: ClassAtAddressQuery, context: RpcContext| async move {
+                let block_id = query.block_id().map_err(warp::reject::custom)?;
+                get_class_by_address(context, query.contract_address, block_id)
+                    .await
+                    .map(class_definition_reply)
+                    .map_err(warp::reject::custom)
+            },
+        );
+
+    // This server only exists to mirror the class-sync routes (get by hash,
+    // get by address) that other pathfinder nodes and tooling pull from; it's
+    // not a general feeder-gateway replacement. `get_contract_addresses` is
+    // intentionally left unsupported rather than quietly reimplemented here.
+    let get_contract_addresses = warp::path!("feeder_gateway" / "get_contract_addresses")
+        .and(warp::get())
+        .map(|| {
+            warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({
+                    "error": "get_contract_addresses is not supported by this feeder-gateway compatibility server"
+                })),
+                warp::http::StatusCode::NOT_IMPLEMENTED,
+            )
+        });
+
+    get_class_by_hash
+        .or(get_class_by_address)
+        .unify()
+        .or(get_contract_addresses)
+        .unify()
+}
+
+fn class_definition_reply(definition: Vec<u8>) -> warp::reply::Response {
+    warp::reply::with_header(definition, "content-type", "application/octet-stream").into_response()
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct ClassByHashQuery {
+    #[serde(rename = "classHash")]
+    class_hash: ClassHash,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct ClassAtAddressQuery {
+    #[serde(rename = "contractAddress")]
+    contract_address: ContractAddress,
+    #[serde(rename = "blockNumber", default)]
+    block_number: Option<String>,
+}
+
+impl ClassAtAddressQuery {
+    /// Parses the `blockNumber` query parameter the way the real feeder
+    /// gateway does: `"pending"`/`"latest"`, a decimal block number, or a
+    /// `0x`-prefixed block hash. Anything else is a parse error rather than
+    /// a silent fallback to latest.
+    fn block_id(&self) -> Result<BlockId, FeederGatewayError> {
+        use pathfinder_common::{BlockHash, BlockNumber};
+
+        match self.block_number.as_deref() {
+            None | Some("latest") => Ok(BlockId::Latest),
+            Some("pending") => Ok(BlockId::Pending),
+            Some(s) if s.starts_with("0x") => {
+                let hash = stark_hash::StarkHash::from_hex_str(s)
+                    .map_err(|_| FeederGatewayError::InvalidBlockId)?;
+                Ok(BlockId::Hash(BlockHash(hash)))
+            }
+            Some(s) => {
+                let number: u64 = s.parse().map_err(|_| FeederGatewayError::InvalidBlockId)?;
+                let number =
+                    BlockNumber::new(number).ok_or(FeederGatewayError::InvalidBlockId)?;
+                Ok(BlockId::Number(number))
+            }
+        }
+    }
+}
+
+/// Reads and decompresses a class definition by its hash, exactly as stored.
+async fn get_class_by_hash(
+    context: RpcContext,
+    class_hash: ClassHash,
+) -> Result<Vec<u8>, FeederGatewayError> {
+    let jh = tokio::task::spawn_blocking(move || {
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        tx.class_definition(class_hash)
+            .context("Fetching class definition")?
+            .ok_or(FeederGatewayError::ClassHashNotFound)
+    });
+
+    jh.await.context("Reading class from database")?
+}
+
+/// Resolves the class declared at `contract_address` as of `block_id`, then
+/// reads and decompresses its stored definition. Mirrors `get_class_at`'s resolution
+/// logic, including the `pending_data` overlay used by `get_pending_class_hash`.
+async fn get_class_by_address(
+    context: RpcContext,
+    contract_address: ContractAddress,
+    block_id: BlockId,
+) -> Result<Vec<u8>, FeederGatewayError> {
+    let storage_block_id = match block_id {
+        BlockId::Pending => pathfinder_storage::BlockId::Latest,
+        other => other
+            .try_into()
+            .expect("Only pending cast to storage block id should fail"),
+    };
+
+    // Mirrors `get_pending_class_hash` in `get_class_at`: a contract deployed in
+    // the pending block shadows whatever is already committed to storage.
+    let pending_class_hash = if block_id == BlockId::Pending {
+        match context.pending_data.clone() {
+            Some(pending) => pending.state_update().await.and_then(|state_update| {
+                state_update
+                    .state_diff
+                    .deployed_contracts
+                    .iter()
+                    .find_map(|contract| {
+                        (contract.address == contract_address).then_some(contract.class_hash)
+                    })
+            }),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    let jh = tokio::task::spawn_blocking(move || {
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if !tx.block_exists(storage_block_id)? {
+            return Err(FeederGatewayError::BlockNotFound);
+        }
+
+        let class_hash = match pending_class_hash {
+            Some(class_hash) => class_hash,
+            None => tx
+                .contract_class_hash(storage_block_id, contract_address)
+                .context("Querying contract's class hash")?
+                .ok_or(FeederGatewayError::ClassHashNotFound)?,
+        };
+
+        tx.class_definition(class_hash)
+            .context("Fetching class definition")?
+            .ok_or(FeederGatewayError::ClassHashNotFound)
+    });
+
+    jh.await.context("Reading class from database")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pathfinder_common::{felt, felt_bytes, BlockHash, BlockNumber};
+
+    mod parsing {
+        use super::*;
+
+        fn query(block_number: Option<&str>) -> ClassAtAddressQuery {
+            ClassAtAddressQuery {
+                contract_address: ContractAddress::new_or_panic(felt_bytes!(b"contract 0")),
+                block_number: block_number.map(str::to_owned),
+            }
+        }
+
+        #[test]
+        fn defaults_to_latest() {
+            assert_eq!(query(None).block_id().unwrap(), BlockId::Latest);
+            assert_eq!(query(Some("latest")).block_id().unwrap(), BlockId::Latest);
+        }
+
+        #[test]
+        fn pending() {
+            assert_eq!(query(Some("pending")).block_id().unwrap(), BlockId::Pending);
+        }
+
+        #[test]
+        fn numeric_block_number() {
+            assert_eq!(
+                query(Some("5")).block_id().unwrap(),
+                BlockId::Number(BlockNumber::new_or_panic(5))
+            );
+        }
+
+        #[test]
+        fn block_hash() {
+            assert_eq!(
+                query(Some("0xabcde")).block_id().unwrap(),
+                BlockId::Hash(BlockHash(felt!("0xabcde")))
+            );
+        }
+
+        #[test]
+        fn garbage_is_rejected() {
+            assert_matches!(
+                query(Some("not-a-block")).block_id(),
+                Err(FeederGatewayError::InvalidBlockId)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn get_class_by_hash_serves_stored_definition() {
+        let context = RpcContext::for_tests();
+
+        // Resolve a real class hash via the same contract/block fixtures used
+        // by `get_class_at`'s tests, then fetch it directly by hash.
+        let mut db = context.storage.connection().unwrap();
+        let tx = db.transaction().unwrap();
+        let class_hash = tx
+            .contract_class_hash(
+                pathfinder_storage::BlockId::Latest,
+                ContractAddress::new_or_panic(felt_bytes!(b"contract 0")),
+            )
+            .unwrap()
+            .unwrap();
+        drop(tx);
+        drop(db);
+
+        let definition = get_class_by_hash(context, class_hash).await.unwrap();
+        assert!(!definition.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_class_by_hash_unknown() {
+        let context = RpcContext::for_tests();
+
+        let error = get_class_by_hash(context, ClassHash(stark_hash::StarkHash::ZERO))
+            .await
+            .unwrap_err();
+        assert_matches!(error, FeederGatewayError::ClassHashNotFound);
+    }
+
+    #[tokio::test]
+    async fn get_class_by_address_latest() {
+        let context = RpcContext::for_tests();
+
+        let valid = ContractAddress::new_or_panic(felt_bytes!(b"contract 0"));
+        let definition = get_class_by_address(context, valid, BlockId::Latest)
+            .await
+            .unwrap();
+        assert!(!definition.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_class_by_address_unknown_contract() {
+        let context = RpcContext::for_tests();
+
+        let invalid = ContractAddress::new_or_panic(felt_bytes!(b"invalid"));
+        let error = get_class_by_address(context, invalid, BlockId::Latest)
+            .await
+            .unwrap_err();
+        assert_matches!(error, FeederGatewayError::ClassHashNotFound);
+    }
+
+    #[tokio::test]
+    async fn get_class_by_address_unknown_block() {
+        let context = RpcContext::for_tests();
+
+        let valid = ContractAddress::new_or_panic(felt_bytes!(b"contract 0"));
+        let error = get_class_by_address(context, valid, BlockId::Number(BlockNumber::MAX))
+            .await
+            .unwrap_err();
+        assert_matches!(error, FeederGatewayError::BlockNotFound);
+    }
+}
@@ -1,20 +1,62 @@
 use crate::core::{ClassHash, StarknetTransactionHash};
-use crate::rpc::v02::types::request::BroadcastedDeclareTransaction;
+use crate::rpc::v02::types::request::{
+    BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV0V1,
+    BroadcastedDeclareTransactionV2, SierraContractClass,
+};
 use crate::rpc::v02::RpcContext;
 use crate::sequencer::error::SequencerError;
 use crate::sequencer::request::add_transaction::ContractDefinition;
 use crate::sequencer::ClientApi;
 
-crate::rpc::error::generate_rpc_error_subset!(AddDeclareTransactionError: InvalidContractClass);
+impl TryFrom<SierraContractClass> for ContractDefinition {
+    type Error = anyhow::Error;
+
+    /// The gateway's Cairo 1 declare body carries the Sierra program, its
+    /// version tag, entry points and ABI verbatim instead of a compiled
+    /// `program`, so this is a separate conversion from the Cairo 0 one.
+    fn try_from(sierra: SierraContractClass) -> anyhow::Result<Self> {
+        Ok(ContractDefinition {
+            program: None,
+            sierra_program: Some(sierra.sierra_program),
+            contract_class_version: Some(sierra.contract_class_version),
+            entry_points_by_type: sierra.entry_points_by_type,
+            abi: Some(sierra.abi),
+        })
+    }
+}
+
+crate::rpc::error::generate_rpc_error_subset!(
+    AddDeclareTransactionError:
+        ClassAlreadyDeclared(String),
+        CompiledClassHashMismatch(String),
+        InvalidTransactionNonce(String),
+        InsufficientMaxFee(String),
+        InsufficientAccountBalance(String),
+        ValidationFailure(String),
+        InvalidContractClass
+);
 
 impl From<SequencerError> for AddDeclareTransactionError {
     fn from(e: SequencerError) -> Self {
-        use crate::sequencer::error::StarknetErrorCode::InvalidProgram;
+        use crate::sequencer::error::StarknetErrorCode::*;
         match e {
-            SequencerError::StarknetError(e) if e.code == InvalidProgram => {
-                Self::InvalidContractClass
-            }
-            _ => Self::Internal(e.into()),
+            SequencerError::StarknetError(starknet_error) => match starknet_error.code {
+                InvalidProgram => Self::InvalidContractClass,
+                ClassAlreadyDeclared => Self::ClassAlreadyDeclared(starknet_error.message),
+                InvalidCompiledClassHash => {
+                    Self::CompiledClassHashMismatch(starknet_error.message)
+                }
+                InvalidTransactionNonce => Self::InvalidTransactionNonce(starknet_error.message),
+                InsufficientMaxFee => Self::InsufficientMaxFee(starknet_error.message),
+                InsufficientAccountBalance => {
+                    Self::InsufficientAccountBalance(starknet_error.message)
+                }
+                ValidateFailure | TransactionLimitExceeded => {
+                    Self::ValidationFailure(starknet_error.message)
+                }
+                _ => Self::Internal(anyhow::anyhow!(starknet_error.message)),
+            },
+            other => Self::Internal(other.into()),
         }
     }
 }
@@ -42,21 +84,64 @@ pub async fn add_declare_transaction(
     input: AddDeclareTransactionInput,
 ) -> Result<AddDeclareTransactionOutput, AddDeclareTransactionError> {
     let Transaction::Declare(tx) = input.declare_transaction;
-    let contract_definition: ContractDefinition = tx
-        .contract_class
-        .try_into()
-        .map_err(|e| anyhow::anyhow!("Failed to convert contract definition: {}", e))?;
+
+    let (version, max_fee, signature, nonce, contract_definition, sender_address, compiled_class_hash) =
+        match tx {
+            BroadcastedDeclareTransaction::V0V1(BroadcastedDeclareTransactionV0V1 {
+                version,
+                max_fee,
+                signature,
+                nonce,
+                contract_class,
+                sender_address,
+            }) => {
+                let contract_definition: ContractDefinition = contract_class
+                    .try_into()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert contract definition: {}", e))?;
+                (
+                    version,
+                    max_fee,
+                    signature,
+                    nonce,
+                    contract_definition,
+                    sender_address,
+                    None,
+                )
+            }
+            BroadcastedDeclareTransaction::V2(BroadcastedDeclareTransactionV2 {
+                version,
+                max_fee,
+                signature,
+                nonce,
+                contract_class,
+                sender_address,
+                compiled_class_hash,
+            }) => {
+                let contract_definition: ContractDefinition = contract_class
+                    .try_into()
+                    .map_err(|e| anyhow::anyhow!("Failed to convert contract definition: {}", e))?;
+                (
+                    version,
+                    max_fee,
+                    signature,
+                    nonce,
+                    contract_definition,
+                    sender_address,
+                    Some(compiled_class_hash),
+                )
+            }
+        };
 
     let response = context
         .sequencer
         .add_declare_transaction(
-            tx.version,
-            tx.max_fee,
-            tx.signature,
-            tx.nonce,
+            version,
+            max_fee,
+            signature,
+            nonce,
             contract_definition,
-            tx.sender_address,
-            None,
+            sender_address,
+            compiled_class_hash,
         )
         .await?;
 
@@ -70,8 +155,12 @@ pub async fn add_declare_transaction(
 mod tests {
     use stark_hash::StarkHash;
 
-    use crate::core::{ContractAddress, Fee, TransactionNonce, TransactionVersion};
-    use crate::rpc::v02::types::request::BroadcastedDeclareTransaction;
+    use crate::core::{CompiledClassHash, ContractAddress, Fee, TransactionNonce, TransactionVersion};
+    use crate::rpc::v02::types::request::{
+        BroadcastedDeclareTransaction, BroadcastedDeclareTransactionV0V1,
+        BroadcastedDeclareTransactionV2, SierraContractClass, SierraEntryPoint,
+        SierraEntryPoints,
+    };
     use crate::rpc::v02::types::ContractClass;
     use crate::starkhash;
 
@@ -90,20 +179,57 @@ mod tests {
         pub static ref CONTRACT_CLASS_JSON: String = {
             serde_json::to_string(&*CONTRACT_CLASS).unwrap()
         };
+
+        // A minimal but genuine Sierra class, in contrast to `CONTRACT_CLASS`
+        // above which is a Cairo 0 (legacy) definition and cannot exercise the
+        // v2 parsing path.
+        pub static ref SIERRA_CLASS: SierraContractClass = SierraContractClass {
+            sierra_program: vec![StarkHash::from_u64(1), StarkHash::from_u64(2)],
+            contract_class_version: "0.1.0".to_owned(),
+            entry_points_by_type: SierraEntryPoints {
+                constructor: vec![],
+                external: vec![SierraEntryPoint {
+                    selector: StarkHash::from_u64(3),
+                    function_idx: 0,
+                }],
+                l1_handler: vec![],
+            },
+            abi: "[]".to_owned(),
+        };
+
+        pub static ref SIERRA_CLASS_JSON: String = {
+            serde_json::to_string(&*SIERRA_CLASS).unwrap()
+        };
     }
 
     mod parsing {
         use super::*;
 
         fn test_declare_txn() -> Transaction {
-            Transaction::Declare(BroadcastedDeclareTransaction {
-                max_fee: Fee(web3::types::H128::from_low_u64_be(1)),
-                version: TransactionVersion::ZERO,
-                signature: vec![],
-                nonce: TransactionNonce(StarkHash::ZERO),
-                contract_class: CONTRACT_CLASS.clone(),
-                sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
-            })
+            Transaction::Declare(BroadcastedDeclareTransaction::V0V1(
+                BroadcastedDeclareTransactionV0V1 {
+                    max_fee: Fee(web3::types::H128::from_low_u64_be(1)),
+                    version: TransactionVersion::ZERO,
+                    signature: vec![],
+                    nonce: TransactionNonce(StarkHash::ZERO),
+                    contract_class: CONTRACT_CLASS.clone(),
+                    sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
+                },
+            ))
+        }
+
+        fn test_declare_txn_v2() -> Transaction {
+            Transaction::Declare(BroadcastedDeclareTransaction::V2(
+                BroadcastedDeclareTransactionV2 {
+                    max_fee: Fee(web3::types::H128::from_low_u64_be(1)),
+                    version: TransactionVersion::TWO,
+                    signature: vec![],
+                    nonce: TransactionNonce(StarkHash::ZERO),
+                    contract_class: SIERRA_CLASS.clone(),
+                    sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
+                    compiled_class_hash: CompiledClassHash(StarkHash::from_u64(2)),
+                },
+            ))
         }
 
         #[test]
@@ -159,6 +285,102 @@ mod tests {
             };
             assert_eq!(input, expected);
         }
+
+        #[test]
+        fn v2_positional_args() {
+            use jsonrpsee::types::Params;
+
+            let positional = format!(
+                r#"[
+                    {{
+                        "type": "DECLARE",
+                        "version": "0x2",
+                        "max_fee": "0x1",
+                        "signature": [],
+                        "nonce": "0x0",
+                        "contract_class": {},
+                        "sender_address": "0x1",
+                        "compiled_class_hash": "0x2"
+                    }}
+                ]"#,
+                SIERRA_CLASS_JSON.clone()
+            );
+            let positional = Params::new(Some(&positional));
+
+            let input = positional.parse::<AddDeclareTransactionInput>().unwrap();
+            let expected = AddDeclareTransactionInput {
+                declare_transaction: test_declare_txn_v2(),
+            };
+            assert_eq!(input, expected);
+        }
+
+        #[test]
+        fn v2_named_args() {
+            use jsonrpsee::types::Params;
+
+            let named = format!(
+                r#"{{
+                    "declare_transaction": {{
+                        "type": "DECLARE",
+                        "version": "0x2",
+                        "max_fee": "0x1",
+                        "signature": [],
+                        "nonce": "0x0",
+                        "contract_class": {},
+                        "sender_address": "0x1",
+                        "compiled_class_hash": "0x2"
+                    }}
+                }}"#,
+                SIERRA_CLASS_JSON.clone()
+            );
+            let named = Params::new(Some(&named));
+
+            let input = named.parse::<AddDeclareTransactionInput>().unwrap();
+            let expected = AddDeclareTransactionInput {
+                declare_transaction: test_declare_txn_v2(),
+            };
+            assert_eq!(input, expected);
+        }
+    }
+
+    #[test]
+    fn error_mapping_preserves_reason() {
+        use crate::sequencer::error::{StarknetError, StarknetErrorCode};
+
+        let reason = "Class with hash 0x1234 is already declared.".to_owned();
+
+        let map = |code: StarknetErrorCode| -> AddDeclareTransactionError {
+            SequencerError::StarknetError(StarknetError {
+                code,
+                message: reason.clone(),
+            })
+            .into()
+        };
+
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::ClassAlreadyDeclared),
+            AddDeclareTransactionError::ClassAlreadyDeclared(msg) => assert_eq!(msg, reason)
+        );
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::InvalidCompiledClassHash),
+            AddDeclareTransactionError::CompiledClassHashMismatch(msg) => assert_eq!(msg, reason)
+        );
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::InvalidTransactionNonce),
+            AddDeclareTransactionError::InvalidTransactionNonce(msg) => assert_eq!(msg, reason)
+        );
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::InsufficientMaxFee),
+            AddDeclareTransactionError::InsufficientMaxFee(msg) => assert_eq!(msg, reason)
+        );
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::InsufficientAccountBalance),
+            AddDeclareTransactionError::InsufficientAccountBalance(msg) => assert_eq!(msg, reason)
+        );
+        assert_matches::assert_matches!(
+            map(StarknetErrorCode::ValidateFailure),
+            AddDeclareTransactionError::ValidationFailure(msg) => assert_eq!(msg, reason)
+        );
     }
 
     #[test_log::test(tokio::test)]
@@ -170,14 +392,16 @@ mod tests {
             ..CONTRACT_CLASS.clone()
         };
 
-        let declare_transaction = Transaction::Declare(BroadcastedDeclareTransaction {
-            version: TransactionVersion::ZERO,
-            max_fee: Fee(Default::default()),
-            signature: vec![],
-            nonce: TransactionNonce(Default::default()),
-            contract_class: invalid_contract_class,
-            sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
-        });
+        let declare_transaction = Transaction::Declare(BroadcastedDeclareTransaction::V0V1(
+            BroadcastedDeclareTransactionV0V1 {
+                version: TransactionVersion::ZERO,
+                max_fee: Fee(Default::default()),
+                signature: vec![],
+                nonce: TransactionNonce(Default::default()),
+                contract_class: invalid_contract_class,
+                sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
+            },
+        ));
 
         let input = AddDeclareTransactionInput {
             declare_transaction,
@@ -190,14 +414,16 @@ mod tests {
     async fn successful_declare() {
         let context = RpcContext::for_tests();
 
-        let declare_transaction = Transaction::Declare(BroadcastedDeclareTransaction {
-            version: TransactionVersion::ZERO,
-            max_fee: Fee(Default::default()),
-            signature: vec![],
-            nonce: TransactionNonce(Default::default()),
-            contract_class: CONTRACT_CLASS.clone(),
-            sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
-        });
+        let declare_transaction = Transaction::Declare(BroadcastedDeclareTransaction::V0V1(
+            BroadcastedDeclareTransactionV0V1 {
+                version: TransactionVersion::ZERO,
+                max_fee: Fee(Default::default()),
+                signature: vec![],
+                nonce: TransactionNonce(Default::default()),
+                contract_class: CONTRACT_CLASS.clone(),
+                sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
+            },
+        ));
 
         let input = AddDeclareTransactionInput {
             declare_transaction,
@@ -215,4 +441,30 @@ mod tests {
             }
         );
     }
+
+    #[test_log::test(tokio::test)]
+    async fn successful_declare_v2() {
+        let context = RpcContext::for_tests();
+
+        let declare_transaction = Transaction::Declare(BroadcastedDeclareTransaction::V2(
+            BroadcastedDeclareTransactionV2 {
+                version: TransactionVersion::TWO,
+                max_fee: Fee(Default::default()),
+                signature: vec![],
+                nonce: TransactionNonce(Default::default()),
+                contract_class: SIERRA_CLASS.clone(),
+                sender_address: ContractAddress::new_or_panic(StarkHash::from_u64(1)),
+                compiled_class_hash: CompiledClassHash(StarkHash::from_u64(2)),
+            },
+        ));
+
+        let input = AddDeclareTransactionInput {
+            declare_transaction,
+        };
+
+        // Mainly exercising that the v2 path's extra `compiled_class_hash`
+        // field makes it all the way through to `add_declare_transaction`
+        // instead of being dropped during the match-arm conversion.
+        add_declare_transaction(context, input).await.unwrap();
+    }
 }
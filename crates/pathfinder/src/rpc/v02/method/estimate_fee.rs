@@ -0,0 +1,86 @@
+use crate::core::{Fee, GasPrice};
+use crate::rpc::v02::method::add_declare_transaction::Transaction;
+use crate::rpc::v02::method::execution_state::{execution_state, ExecutionStateError};
+use crate::rpc::v02::RpcContext;
+use anyhow::Context;
+use pathfinder_common::BlockId;
+
+crate::rpc::error::generate_rpc_error_subset!(
+    EstimateFeeError: BlockNotFound, ContractNotFound, ContractError
+);
+
+impl From<ExecutionStateError> for EstimateFeeError {
+    fn from(e: ExecutionStateError) -> Self {
+        match e {
+            ExecutionStateError::BlockNotFound => Self::BlockNotFound,
+            ExecutionStateError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+/// Skips transaction validation and/or fee charging, so that accounts
+/// without sufficient balance can still be estimated against.
+#[derive(serde::Deserialize, Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct SimulationFlags {
+    #[serde(default)]
+    pub skip_validate: bool,
+    #[serde(default)]
+    pub skip_fee_charge: bool,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct EstimateFeeInput {
+    request: Vec<Transaction>,
+    block_id: BlockId,
+    #[serde(default)]
+    simulation_flags: SimulationFlags,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq, Clone)]
+pub struct FeeEstimate {
+    pub gas_consumed: Fee,
+    pub gas_price: GasPrice,
+    pub overall_fee: Fee,
+}
+
+pub async fn estimate_fee(
+    context: RpcContext,
+    input: EstimateFeeInput,
+) -> Result<Vec<FeeEstimate>, EstimateFeeError> {
+    let span = tracing::Span::current();
+
+    let jh = tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let state = execution_state(
+            &tx,
+            &context,
+            input.block_id,
+            context.pending_data.clone(),
+            input.simulation_flags.skip_validate,
+            input.simulation_flags.skip_fee_charge,
+        )?;
+
+        input
+            .request
+            .into_iter()
+            .map(|Transaction::Declare(declare)| {
+                pathfinder_executor::estimate_fee(&state, declare)
+                    .context("Estimating fee")
+                    .map(|estimate| FeeEstimate {
+                        gas_consumed: estimate.gas_consumed,
+                        gas_price: estimate.gas_price,
+                        overall_fee: estimate.overall_fee,
+                    })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(EstimateFeeError::from)
+    });
+
+    jh.await.context("Estimating fee")?
+}
@@ -0,0 +1,65 @@
+use crate::rpc::v02::RpcContext;
+use anyhow::Context;
+use pathfinder_common::BlockId;
+use pathfinder_executor::ExecutionState;
+use starknet_gateway_types::pending::PendingData;
+
+/// Error produced while resolving a [BlockId] and building an [ExecutionState].
+/// Kept distinct from any one RPC method's error type so that both
+/// `estimate_fee` and `simulate_transactions` can map `BlockNotFound` onto
+/// their own generated error enum instead of collapsing it into `Internal`.
+pub(super) enum ExecutionStateError {
+    BlockNotFound,
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for ExecutionStateError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Internal(e)
+    }
+}
+
+/// Maps an RPC [BlockId] to the storage variant and builds the [ExecutionState]
+/// used by `starknet_estimateFee` and `starknet_simulateTransactions`.
+///
+/// This mirrors the block resolution done in `get_class_at`: pending reads
+/// fall back to latest in storage, with `pending_data` layered on top, and a
+/// missing block is reported as `BlockNotFound` rather than an opaque error.
+pub(super) fn execution_state(
+    tx: &pathfinder_storage::Transaction<'_>,
+    context: &RpcContext,
+    block_id: BlockId,
+    pending_data: Option<PendingData>,
+    skip_validate: bool,
+    skip_fee_charge: bool,
+) -> Result<ExecutionState, ExecutionStateError> {
+    let storage_block_id = match block_id {
+        BlockId::Pending => pathfinder_storage::BlockId::Latest,
+        other => other
+            .try_into()
+            .context("Only pending cast to storage block id should fail")?,
+    };
+
+    if !tx
+        .block_exists(storage_block_id)
+        .context("Querying block existence")?
+    {
+        return Err(ExecutionStateError::BlockNotFound);
+    }
+
+    let header = tx
+        .block_header(storage_block_id)
+        .context("Querying block header")?
+        .context("Block header missing from database")?;
+
+    ExecutionState::new(
+        tx,
+        context.chain_id,
+        header,
+        (block_id == BlockId::Pending).then_some(pending_data).flatten(),
+        skip_validate,
+        skip_fee_charge,
+    )
+    .context("Building execution state")
+    .map_err(Into::into)
+}
@@ -0,0 +1,86 @@
+use crate::rpc::v02::method::add_declare_transaction::Transaction;
+use crate::rpc::v02::method::estimate_fee::{FeeEstimate, SimulationFlags};
+use crate::rpc::v02::method::execution_state::{execution_state, ExecutionStateError};
+use crate::rpc::v02::RpcContext;
+use anyhow::Context;
+use pathfinder_common::BlockId;
+
+crate::rpc::error::generate_rpc_error_subset!(
+    SimulateTransactionError: BlockNotFound, ContractNotFound, ContractError
+);
+
+impl From<ExecutionStateError> for SimulateTransactionError {
+    fn from(e: ExecutionStateError) -> Self {
+        match e {
+            ExecutionStateError::BlockNotFound => Self::BlockNotFound,
+            ExecutionStateError::Internal(e) => Self::Internal(e),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct SimulateTransactionsInput {
+    block_id: BlockId,
+    transactions: Vec<Transaction>,
+    #[serde(default)]
+    simulation_flags: SimulationFlags,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub struct SimulatedTransaction {
+    pub fee_estimate: FeeEstimate,
+    /// The execution trace produced by the local VM run of this transaction.
+    pub transaction_trace: pathfinder_executor::TransactionTrace,
+    /// The state diff produced by this transaction.
+    pub state_diff: Option<pathfinder_executor::StateDiff>,
+}
+
+pub async fn simulate_transactions(
+    context: RpcContext,
+    input: SimulateTransactionsInput,
+) -> Result<Vec<SimulatedTransaction>, SimulateTransactionError> {
+    let span = tracing::Span::current();
+
+    let jh = tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        let state = execution_state(
+            &tx,
+            &context,
+            input.block_id,
+            context.pending_data.clone(),
+            input.simulation_flags.skip_validate,
+            input.simulation_flags.skip_fee_charge,
+        )?;
+
+        input
+            .transactions
+            .into_iter()
+            .map(|Transaction::Declare(declare)| {
+                // `simulate` already runs the transaction through the VM once and
+                // reports the fee it actually charged, so there's no need for a
+                // separate `estimate_fee` pass over the same transaction.
+                let (estimate, trace, state_diff) = pathfinder_executor::simulate(&state, declare)
+                    .context("Simulating transaction")?;
+
+                Ok(SimulatedTransaction {
+                    fee_estimate: FeeEstimate {
+                        gas_consumed: estimate.gas_consumed,
+                        gas_price: estimate.gas_price,
+                        overall_fee: estimate.overall_fee,
+                    },
+                    transaction_trace: trace,
+                    state_diff,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(Into::into)
+    });
+
+    jh.await.context("Simulating transactions")?
+}
@@ -0,0 +1,108 @@
+use crate::core::{
+    CompiledClassHash, ContractAddress, Fee, TransactionNonce, TransactionSignatureElem,
+    TransactionVersion,
+};
+use crate::rpc::v02::types::ContractClass;
+use stark_hash::StarkHash;
+
+/// A version-dispatching wrapper for `DECLARE` transactions broadcast via JSON-RPC.
+///
+/// Starknet 0.11 introduced the Cairo 1 (Sierra) declare shape alongside the
+/// original Cairo 0 one, and the two carry different payloads (a compiled
+/// program vs. a Sierra program plus its CASM `compiled_class_hash`). Rather
+/// than coercing both into one struct, we peek at `version` and deserialize
+/// into the matching variant.
+///
+/// `DECLARE` v3 (the resource-bounds fee model introduced later) is not
+/// modeled here yet and is rejected with a deserialization error rather than
+/// silently misparsed as v2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BroadcastedDeclareTransaction {
+    V0V1(BroadcastedDeclareTransactionV0V1),
+    V2(BroadcastedDeclareTransactionV2),
+}
+
+impl<'de> serde::Deserialize<'de> for BroadcastedDeclareTransaction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Version {
+            version: TransactionVersion,
+        }
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let version = Version::deserialize(&value)
+            .map_err(serde::de::Error::custom)?
+            .version;
+
+        match version.without_query_version() {
+            0 | 1 => Ok(Self::V0V1(
+                BroadcastedDeclareTransactionV0V1::deserialize(value)
+                    .map_err(serde::de::Error::custom)?,
+            )),
+            2 => Ok(Self::V2(
+                BroadcastedDeclareTransactionV2::deserialize(value)
+                    .map_err(serde::de::Error::custom)?,
+            )),
+            other => Err(serde::de::Error::custom(format!(
+                "unsupported declare transaction version {other} (supported: 0, 1, 2)"
+            ))),
+        }
+    }
+}
+
+/// The original (Cairo 0) `DECLARE` shape: a compressed, compiled program with no
+/// separate CASM hash.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastedDeclareTransactionV0V1 {
+    pub version: TransactionVersion,
+    pub max_fee: Fee,
+    pub signature: Vec<TransactionSignatureElem>,
+    pub nonce: TransactionNonce,
+    pub contract_class: ContractClass,
+    pub sender_address: ContractAddress,
+}
+
+/// The Cairo 1 (Sierra) `DECLARE` shape: a Sierra class definition plus the
+/// `compiled_class_hash` of the CASM the sequencer compiles it down to.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastedDeclareTransactionV2 {
+    pub version: TransactionVersion,
+    pub max_fee: Fee,
+    pub signature: Vec<TransactionSignatureElem>,
+    pub nonce: TransactionNonce,
+    pub contract_class: SierraContractClass,
+    pub sender_address: ContractAddress,
+    pub compiled_class_hash: CompiledClassHash,
+}
+
+/// A Cairo 1 (Sierra) class definition, as carried by a `DECLARE` v2 body.
+///
+/// Unlike the legacy [ContractClass] (a compiled Cairo 0 `program`), this
+/// wraps the Sierra program itself plus its version tag — the shapes don't
+/// overlap, so v2 needs its own type rather than reusing `ContractClass`.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SierraContractClass {
+    pub sierra_program: Vec<StarkHash>,
+    pub contract_class_version: String,
+    pub entry_points_by_type: SierraEntryPoints,
+    pub abi: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SierraEntryPoints {
+    #[serde(default)]
+    pub constructor: Vec<SierraEntryPoint>,
+    #[serde(default)]
+    pub external: Vec<SierraEntryPoint>,
+    #[serde(default)]
+    pub l1_handler: Vec<SierraEntryPoint>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SierraEntryPoint {
+    pub selector: StarkHash,
+    pub function_idx: u64,
+}
@@ -0,0 +1,207 @@
+use crate::context::RpcContext;
+use anyhow::Context;
+use pathfinder_common::{
+    BlockId, ClassCommitment, ClassHash, ContractAddress, ContractNonce, ContractStateHash,
+    StateCommitment, StorageAddress,
+};
+use stark_hash::StarkHash;
+
+crate::error::generate_rpc_error_subset!(GetProofError: BlockNotFound, ContractNotFound, ClassHashNotFound);
+
+/// A node in a binary Merkle-Patricia trie, as returned by [get_proof] and
+/// [get_class_proof].
+///
+/// A proof is the ordered list of [TrieNode]s encountered while walking from
+/// the trie's root down to the leaf being proven. A verifier recomputes each
+/// parent's hash from its children and checks that the final, recomputed
+/// root matches the block's committed root.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "node_type")]
+pub enum TrieNode {
+    Binary { left: StarkHash, right: StarkHash },
+    Edge { path: StarkHash, length: u8, child: StarkHash },
+}
+
+/// Everything needed to reconstruct a contract's leaf hash in the global
+/// state trie: `H(class_hash, storage_root, nonce, contract_state_hash_version)`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct ContractData {
+    pub class_hash: ClassHash,
+    pub nonce: ContractNonce,
+    pub storage_root: StarkHash,
+    pub contract_state_hash_version: StarkHash,
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct GetProofInput {
+    block_id: BlockId,
+    contract_address: ContractAddress,
+    keys: Vec<StorageAddress>,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub struct GetProofOutput {
+    state_commitment: StateCommitment,
+    class_commitment: ClassCommitment,
+    contract_proof: Vec<TrieNode>,
+    contract_data: ContractData,
+    storage_proofs: Vec<Vec<TrieNode>>,
+}
+
+pub async fn get_proof(
+    context: RpcContext,
+    input: GetProofInput,
+) -> Result<GetProofOutput, GetProofError> {
+    let span = tracing::Span::current();
+
+    let block_id = match input.block_id {
+        BlockId::Pending => pathfinder_storage::BlockId::Latest,
+        other => other.try_into().expect("Only pending cast should fail"),
+    };
+
+    let jh = tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if !tx.block_exists(block_id)? {
+            return Err(GetProofError::BlockNotFound);
+        }
+
+        let (state_commitment, class_commitment) = tx
+            .block_commitments(block_id)
+            .context("Querying block commitments")?
+            .ok_or(GetProofError::BlockNotFound)?;
+
+        let (contract_proof, contract_data) = tx
+            .contract_merkle_proof(block_id, input.contract_address)
+            .context("Generating contract proof")?
+            .ok_or(GetProofError::ContractNotFound)?;
+
+        let storage_proofs = input
+            .keys
+            .iter()
+            .map(|key| {
+                tx.contract_storage_merkle_proof(block_id, input.contract_address, *key)
+                    .context("Generating storage proof")
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(GetProofOutput {
+            state_commitment,
+            class_commitment,
+            contract_proof,
+            contract_data,
+            storage_proofs,
+        })
+    });
+
+    jh.await.context("Generating proof")?
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Eq)]
+pub struct GetClassProofInput {
+    block_id: BlockId,
+    class_hash: ClassHash,
+}
+
+#[derive(serde::Serialize, Debug, PartialEq, Eq)]
+pub struct GetClassProofOutput {
+    class_commitment: ClassCommitment,
+    class_proof: Vec<TrieNode>,
+}
+
+pub async fn get_class_proof(
+    context: RpcContext,
+    input: GetClassProofInput,
+) -> Result<GetClassProofOutput, GetProofError> {
+    let span = tracing::Span::current();
+
+    let block_id = match input.block_id {
+        BlockId::Pending => pathfinder_storage::BlockId::Latest,
+        other => other.try_into().expect("Only pending cast should fail"),
+    };
+
+    let jh = tokio::task::spawn_blocking(move || {
+        let _g = span.enter();
+        let mut db = context
+            .storage
+            .connection()
+            .context("Opening database connection")?;
+
+        let tx = db.transaction().context("Creating database transaction")?;
+
+        if !tx.block_exists(block_id)? {
+            return Err(GetProofError::BlockNotFound);
+        }
+
+        let (_, class_commitment) = tx
+            .block_commitments(block_id)
+            .context("Querying block commitments")?
+            .ok_or(GetProofError::BlockNotFound)?;
+
+        let class_proof = tx
+            .class_merkle_proof(block_id, input.class_hash)
+            .context("Generating class proof")?
+            .ok_or(GetProofError::ClassHashNotFound)?;
+
+        Ok(GetClassProofOutput {
+            class_commitment,
+            class_proof,
+        })
+    });
+
+    jh.await.context("Generating class proof")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::Params;
+    use pathfinder_common::{felt, BlockHash};
+
+    mod parsing {
+        use super::*;
+
+        #[test]
+        fn get_proof_positional_args() {
+            let positional = r#"[
+                { "block_hash": "0xabcde" },
+                "0x12345",
+                ["0x1", "0x2"]
+            ]"#;
+            let positional = Params::new(Some(positional));
+
+            let input = positional.parse::<GetProofInput>().unwrap();
+            let expected = GetProofInput {
+                block_id: BlockHash(felt!("0xabcde")).into(),
+                contract_address: ContractAddress::new_or_panic(felt!("0x12345")),
+                keys: vec![
+                    StorageAddress::new_or_panic(felt!("0x1")),
+                    StorageAddress::new_or_panic(felt!("0x2")),
+                ],
+            };
+            assert_eq!(input, expected);
+        }
+
+        #[test]
+        fn get_class_proof_named_args() {
+            let named = r#"{
+                "block_id": { "block_hash": "0xabcde" },
+                "class_hash": "0x12345"
+            }"#;
+            let named = Params::new(Some(named));
+
+            let input = named.parse::<GetClassProofInput>().unwrap();
+            let expected = GetClassProofInput {
+                block_id: BlockHash(felt!("0xabcde")).into(),
+                class_hash: ClassHash(felt!("0x12345")),
+            };
+            assert_eq!(input, expected);
+        }
+    }
+}